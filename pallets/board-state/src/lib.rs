@@ -1,28 +1,21 @@
-//! # Template Pallet
+//! # Board-State Pallet
 //!
-//! A pallet with minimal functionality to help developers understand the essential components of
-//! writing a FRAME pallet. It is typically used in beginner tutorials or in Substrate template
-//! nodes as a starting point for creating a new pallet and **not meant to be used in production**.
+//! A pallet implementing an imageboard-style content system, where posts must pass through a
+//! data-availability attestation pipeline before becoming permanent.
 //!
 //! ## Overview
 //!
-//! This template pallet contains basic examples of:
-//! - declaring a storage item that stores a single `u32` value
-//! - declaring and using events
-//! - declaring and using errors
-//! - a dispatchable function that allows a user to set a new value to storage and emits an event
-//!   upon success
-//! - another dispatchable function that causes a custom error to be thrown
-//!
-//! Each pallet section is annotated with an attribute using the `#[pallet::...]` procedural macro.
-//! This macro generates the necessary code for a pallet to be aggregated into a FRAME runtime.
-//!
-//! Learn more about FRAME macros [here](https://docs.substrate.io/reference/frame-macros/).
+//! This pallet models boards, threads and posts:
+//! - a **board** has a name, description and rules, and is organised into threads
+//! - a **thread** is a fixed-capacity ring buffer of posts (`posts_per_thread`); once full, new
+//!   posts overwrite the oldest slot and bump the thread
+//! - a **post** submitted with `submit_post` is never written directly into permanent storage.
+//!   It is first appended to the board's post buffer (`BufferedPosts`) and must clear a
+//!   commit-reveal availability attestation before being promoted into `Post` storage. A
+//!   thread's first post, given to `create_thread`, is written into `Post` directly.
 //!
 //! ### Pallet Sections
 //!
-//! The pallet sections in this template are:
-//!
 //! - A **configuration trait** that defines the types and parameters which the pallet depends on
 //!   (denoted by the `#[pallet::config]` attribute). See: [`Config`].
 //! - A **means to store pallet-specific data** (denoted by the `#[pallet::storage]` attribute).
@@ -33,8 +26,6 @@
 //!   attribute). See: [`Error`].
 //! - A **set of dispatchable functions** that define the pallet's functionality (denoted by the
 //!   `#[pallet::call]` attribute). See: [`dispatchables`].
-//!
-//! Run `cargo doc --package pallet-template --open` to view this pallet's documentation.
 
 // We make sure this pallet uses `no_std` for compiling to Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -60,30 +51,63 @@ mod benchmarking;
 pub mod weights;
 pub use weights::*;
 
+/// The offchain-worker key type under which attester signing keys are stored in the local
+/// keystore.
+pub const KEY_TYPE: sp_application_crypto::KeyTypeId = sp_application_crypto::KeyTypeId(*b"bdst");
+
+/// Offchain-worker signing primitives for this pallet.
+///
+/// Attester nodes generate a key of this type and insert it into their keystore so the
+/// `offchain_worker` hook can sign commit/reveal transactions on their behalf.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_application_crypto::{app_crypto, sr25519};
+	use sp_runtime::{MultiSignature, MultiSigner};
+
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// Identifies the `sr25519`-based offchain-worker signing scheme used by attesters.
+	pub struct AttesterAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for AttesterAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
 // All pallet logic is defined in its own module and must be annotated by the `pallet` attribute.
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
 	// Import various useful types required by all FRAME pallets.
 	use super::*;
-	use frame_support::pallet_prelude::*;
-	use frame_system::pallet_prelude::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, Imbalance, ReservableCurrency},
+	};
+	use frame_system::{
+		offchain::{
+			AppCrypto, CreateSignedTransaction, SendUnsignedTransaction, SignedPayload, Signer,
+			SigningTypes,
+		},
+		pallet_prelude::*,
+	};
 	use sp_core::H256;
+	use sp_io::hashing::blake2_256;
+	use sp_runtime::{
+		offchain::{http, Duration},
+		traits::{IdentifyAccount, Randomness, Zero},
+		Percent, RuntimeAppPublic,
+		transaction_validity::{
+			InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
+			ValidTransaction,
+		},
+	};
 
 	// --- Constants ---
 
 	// --- Type Definitions ---
 
-	/// A struct to store a single block-number. Has all the right derives to store it in storage.
-	/// <https://paritytech.github.io/polkadot-sdk/master/polkadot_sdk_docs/reference_docs/frame_storage_derives/index.html>
-	#[derive(
-		Encode, Decode, MaxEncodedLen, TypeInfo, CloneNoBound, PartialEqNoBound, DefaultNoBound,
-	)]
-	#[scale_info(skip_type_params(T))]
-	pub struct CompositeStruct<T: Config> {
-		/// A block number.
-		pub(crate) block_number: BlockNumberFor<T>,
-	}
-
 	/// Index for identifying boards.
 	pub type BoardIndex = u16;
 	/// Index for identifying threads within a board.
@@ -101,6 +125,8 @@ pub mod pallet {
 	/// Shard attester set: A dynamic-size array of AccountIds.
 	pub type Attesters<T: Config> = BoundedVec<T::AccountId, T::AttesterSetSize>;
 
+	/// Use Config associated type for flexibility on board name length.
+	type MaxNameLength<T> = <T as Config>::MaxNameLength;
 	/// Use Config associated type for flexibility on description length.
 	type MaxDescLength<T> = <T as Config>::MaxDescLength;
 	/// Use Config associated type for flexibility on rules length.
@@ -128,8 +154,11 @@ pub mod pallet {
 	pub struct ThreadMetadata<T: Config> {
 		/// The block number when the thread was last bumped (created or last post added).
 		pub bump_time: BlockNumberFor<T>,
-		/// The number of active posts in this thread slot. Used to find the next PostIndex.
+		/// The number of active posts in this thread slot, capped at `posts_per_thread`.
 		pub post_count: PostIndex,
+		/// The ring-buffer slot the next post will be written to, wrapping at `posts_per_thread`
+		/// so that once the thread is full, each new post overwrites the oldest one in rotation.
+		pub next_slot: PostIndex,
 	}
 
 	/// Data associated with a post.
@@ -154,6 +183,12 @@ pub mod pallet {
 		pub board_index: BoardIndex,
 		/// The index of the thread this post belongs to within its board.
 		pub thread_index: ThreadIndex,
+		/// The shard responsible for attesting this post's availability, derived deterministically
+		/// from its `Cid` and the on-chain randomness available at submission time.
+		pub shard: ShardIndex,
+		/// The amount reserved from the author's balance as `PostDeposit`, refunded or slashed at
+		/// finalization time.
+		pub deposit: BalanceOf<T>,
 	}
 
 	/// A vote in the commit phase.
@@ -181,7 +216,11 @@ pub mod pallet {
 	pub struct AttestationData<T: Config> {
 		/// The block number when the post was created.
 		pub created_at: BlockNumberFor<T>,
-		/// The votes for the attestation.
+		/// The shard's attester set as it stood when this attestation was created, snapshotted so
+		/// a later re-election of `ShardAttesters` can't desync `votes`' positional indices from
+		/// the attesters they were committed against.
+		pub committee: Attesters<T>,
+		/// The votes for the attestation, indexed positionally by `committee`.
 		pub votes: BoundedVec<AttestationState<T>, T::AttesterSetSize>,
 	}
 
@@ -196,6 +235,52 @@ pub mod pallet {
 		Invalid,
 	}
 
+	/// A commit, signed by an attester's offchain-worker key, submitted as an unsigned
+	/// transaction with a signed payload.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct CommitPayload<T: SigningTypes> {
+		/// The board the attested post belongs to.
+		pub board: BoardIndex,
+		/// The index of the post within the board's buffer.
+		pub buffer_index: BufferIndex,
+		/// The shard the attester is committing for.
+		pub shard: ShardIndex,
+		/// The attester's commitment hash.
+		pub commitment: H256,
+		/// The public key the payload is signed by; also identifies the attester's `AccountId`.
+		pub public: T::Public,
+	}
+
+	impl<T: SigningTypes> SignedPayload<T> for CommitPayload<T> {
+		fn public(&self) -> T::Public {
+			self.public.clone()
+		}
+	}
+
+	/// A reveal, signed by an attester's offchain-worker key, submitted as an unsigned
+	/// transaction with a signed payload.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct RevealPayload<T: SigningTypes> {
+		/// The board the attested post belongs to.
+		pub board: BoardIndex,
+		/// The index of the post within the board's buffer.
+		pub buffer_index: BufferIndex,
+		/// The shard the attester is revealing for.
+		pub shard: ShardIndex,
+		/// The revealed vote.
+		pub vote: Vote,
+		/// The salt used alongside `vote` to produce the original commitment.
+		pub salt: [u8; 32],
+		/// The public key the payload is signed by; also identifies the attester's `AccountId`.
+		pub public: T::Public,
+	}
+
+	impl<T: SigningTypes> SignedPayload<T> for RevealPayload<T> {
+		fn public(&self) -> T::Public {
+			self.public.clone()
+		}
+	}
+
 	// --- Pallet Definition ---
 	// The `Pallet` struct serves as a placeholder to implement traits, methods and dispatchables
 	// (`Call`s) in this pallet.
@@ -209,12 +294,15 @@ pub mod pallet {
 	/// These types are defined generically and made concrete when the pallet is declared in the
 	/// `runtime/src/lib.rs` file of your chain.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: CreateSignedTransaction<Call<Self>> + frame_system::Config {
 		/// The overarching runtime event type.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// A type representing the weights required by the dispatchables of this pallet.
 		type WeightInfo: WeightInfo;
 
+		/// The identifier type for an offchain-worker attestation authority.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
 		// --- Configurable Constants ---
 
 		/// Maximum length for a board name.
@@ -232,10 +320,92 @@ pub mod pallet {
 		/// Maximum number of attesters per shard.
 		#[pallet::constant]
 		type AttesterSetSize: Get<u32>;
+
+		/// Number of blocks after a post is buffered during which attesters may submit a commit.
+		#[pallet::constant]
+		type CommitWindow: Get<BlockNumberFor<Self>>;
+
+		/// Number of blocks, following the close of the commit window, during which attesters may
+		/// reveal their committed vote.
+		#[pallet::constant]
+		type RevealWindow: Get<BlockNumberFor<Self>>;
+
+		/// Source of on-chain randomness used to elect shard attester sets and to assign posts to
+		/// shards.
+		type Randomness: Randomness<Self::Hash, BlockNumberFor<Self>>;
+
+		/// Maximum number of accounts that may be registered in the attester pool at once.
+		#[pallet::constant]
+		type MaxAttesterPool: Get<u32>;
+
+		/// Number of shards each board is partitioned into.
+		#[pallet::constant]
+		type NumShards: Get<ShardIndex>;
+
+		/// Number of blocks between shard attester-set elections.
+		#[pallet::constant]
+		type ShardEpochLength: Get<BlockNumberFor<Self>>;
+
+		/// The currency used to reserve attester bonds.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The amount reserved from an account's balance while it is a registered attester.
+		#[pallet::constant]
+		type AttesterBond: Get<BalanceOf<Self>>;
+
+		/// The fraction of a shard's `AttesterSetSize` that must reveal `Aye` for a buffered post
+		/// to be promoted into permanent storage once its reveal window closes.
+		#[pallet::constant]
+		type AvailabilityThreshold: Get<Percent>;
+
+		/// Maximum number of buffered posts to finalize (promote or reject) per block.
+		#[pallet::constant]
+		type MaxFinalizationsPerBlock: Get<u32>;
+
+		/// The amount reserved from a post author's balance while their post is buffered,
+		/// refunded on finalization and slashed on rejection.
+		#[pallet::constant]
+		type PostDeposit: Get<BalanceOf<Self>>;
+
+		/// The fraction of `AttesterBond` slashed from an attester who revealed `Invalid` or
+		/// failed to reveal within the reveal window.
+		#[pallet::constant]
+		type AttesterSlashFraction: Get<Percent>;
 	}
 
+	/// Balance type of `T::Currency`.
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// Negative imbalance type of `T::Currency`, produced by slashing.
+	pub type NegativeImbalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+
 	// --- Pallet Storage ---
 
+	#[pallet::storage]
+	#[pallet::getter(fn next_board_index)]
+	/// The `BoardIndex` to be assigned to the next board created.
+	pub type NextBoardIndex<T: Config> = StorageValue<_, BoardIndex, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn attester_pool)]
+	/// The pool of accounts eligible for election into a shard's attester set.
+	pub type AttesterPool<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxAttesterPool>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn attester_bond)]
+	/// The amount actually reserved as `AttesterBond` for each registered attester, looked up
+	/// deterministically at slash/refund time even if `AttesterBond` is later reconfigured.
+	pub type AttesterBonds<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn finalization_cursor)]
+	/// Raw `BufferedPosts` key to resume scanning from on the next finalization pass, so work is
+	/// spread across multiple blocks instead of scanning the whole buffer every time.
+	pub type FinalizationCursor<T: Config> = StorageValue<_, Vec<u8>, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn board)]
 	/// Stores metadata for each board.
@@ -323,7 +493,7 @@ pub mod pallet {
 	/// Key1: BoardIndex
 	/// Key2: BufferIndex
 	/// Key3: ShardIndex
-	/// Value: A bounded vector of attestations.
+	/// Value: AttestationData
 	pub type Attestations<T: Config> = StorageNMap<
 		_,
 		(
@@ -331,7 +501,7 @@ pub mod pallet {
 			NMapKey<Twox64Concat, BufferIndex>,
 			NMapKey<Twox64Concat, ShardIndex>,
 		),
-		BoundedVec<AttestationState<T>, T::AttesterSetSize>,
+		AttestationData<T>,
 	>;
 
 	/// Events that functions in this pallet can emit.
@@ -347,13 +517,99 @@ pub mod pallet {
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// A user has successfully set a new value.
-		SomethingStored {
-			/// The new value set.
-			something: u32,
-			/// The account who set the new value.
+		/// A new board was created.
+		BoardCreated {
+			/// The index of the new board.
+			board_index: BoardIndex,
+			/// The account who created the board.
+			who: T::AccountId,
+		},
+		/// A new thread was created on a board.
+		ThreadCreated {
+			/// The index of the board the thread belongs to.
+			board_index: BoardIndex,
+			/// The index of the new thread.
+			thread_index: ThreadIndex,
+			/// The account who created the thread.
+			who: T::AccountId,
+		},
+		/// A post was appended to a board's buffer, pending availability attestation.
+		PostBuffered {
+			/// The index of the board the post was submitted to.
+			board_index: BoardIndex,
+			/// The index of the thread the post was submitted to.
+			thread_index: ThreadIndex,
+			/// The index of the post within the board's buffer.
+			buffer_index: BufferIndex,
+			/// The account who submitted the post.
+			who: T::AccountId,
+		},
+		/// An attester committed a vote on a buffered post's availability.
+		AttestationCommitted {
+			/// The index of the board the post belongs to.
+			board_index: BoardIndex,
+			/// The index of the post within the board's buffer.
+			buffer_index: BufferIndex,
+			/// The shard the attester is committing for.
+			shard: ShardIndex,
+			/// The attester who committed.
 			who: T::AccountId,
 		},
+		/// An attester revealed their committed vote on a buffered post's availability.
+		AttestationRevealed {
+			/// The index of the board the post belongs to.
+			board_index: BoardIndex,
+			/// The index of the post within the board's buffer.
+			buffer_index: BufferIndex,
+			/// The shard the attester revealed for.
+			shard: ShardIndex,
+			/// The attester who revealed.
+			who: T::AccountId,
+			/// The revealed vote, or `Invalid` if it did not match the commitment.
+			vote: RevealedVote,
+		},
+		/// An account joined the attester pool.
+		AttesterRegistered {
+			/// The account who registered.
+			who: T::AccountId,
+		},
+		/// An account left the attester pool.
+		AttesterUnregistered {
+			/// The account who unregistered.
+			who: T::AccountId,
+		},
+		/// A shard's attester set was (re-)elected.
+		ShardAttestersElected {
+			/// The board the shard belongs to.
+			board_index: BoardIndex,
+			/// The shard that was elected.
+			shard: ShardIndex,
+		},
+		/// A buffered post met `AvailabilityThreshold` and was promoted into permanent storage.
+		PostFinalized {
+			/// The board the post belongs to.
+			board_index: BoardIndex,
+			/// The thread the post was promoted into.
+			thread_index: ThreadIndex,
+			/// The buffer index the post was promoted from.
+			buffer_index: BufferIndex,
+		},
+		/// A buffered post did not meet `AvailabilityThreshold` and was dropped.
+		PostRejected {
+			/// The board the post belonged to.
+			board_index: BoardIndex,
+			/// The thread the post was submitted to.
+			thread_index: ThreadIndex,
+			/// The buffer index the post was dropped from.
+			buffer_index: BufferIndex,
+		},
+		/// An author reclaimed a buffered post that was stuck past its reveal window.
+		BufferedPostReclaimed {
+			/// The board the post belonged to.
+			board_index: BoardIndex,
+			/// The buffer index the post was reclaimed from.
+			buffer_index: BufferIndex,
+		},
 	}
 
 	/// Errors that can be returned by this pallet.
@@ -361,15 +617,53 @@ pub mod pallet {
 	/// Errors tell users that something went wrong so it's important that their naming is
 	/// informative. Similar to events, error documentation is added to a node's metadata so it's
 	/// equally important that they have helpful documentation associated with them.
-	///
-	/// This type of runtime error can be up to 4 bytes in size should you want to return additional
-	/// information.
 	#[pallet::error]
 	pub enum Error<T> {
-		/// The value retrieved was `None` as no value was previously set.
-		NoneValue,
-		/// There was an attempt to increment the value in storage over `u32::MAX`.
-		StorageOverflow,
+		/// No board exists at the given `BoardIndex`.
+		BoardNotFound,
+		/// No thread exists at the given `ThreadIndex` on the given board.
+		ThreadNotFound,
+		/// The supplied board name exceeds `MaxNameLength`.
+		NameTooLong,
+		/// The supplied board description exceeds `MaxDescLength`.
+		DescTooLong,
+		/// The supplied board rules exceed `MaxRulesLength`.
+		RulesTooLong,
+		/// A thread's `posts_per_thread` is zero, so it can never hold a post.
+		ThreadFull,
+		/// The board already has `ThreadIndex::MAX` threads.
+		TooManyThreads,
+		/// The chain already has `BoardIndex::MAX` boards.
+		TooManyBoards,
+		/// The board's buffer head has reached `BufferIndex::MAX`. Buffer indices are never
+		/// reused, so this counts every post ever submitted to the board, not only pending ones.
+		BufferOverflow,
+		/// No attester set exists for the given `(board, shard)`.
+		ShardNotFound,
+		/// The caller is not a member of the shard's attester set.
+		NotAttester,
+		/// The commit window for this attestation has closed.
+		CommitWindowClosed,
+		/// The reveal window for this attestation has not opened yet.
+		RevealWindowNotOpen,
+		/// The reveal window for this attestation has closed.
+		RevealWindowClosed,
+		/// The caller has already submitted two commits for this attestation.
+		AlreadyCommitted,
+		/// The caller has not committed a vote for this attestation, so there is nothing to reveal.
+		NoCommitment,
+		/// The account is already registered in the attester pool.
+		AlreadyRegistered,
+		/// The account is not registered in the attester pool.
+		NotRegistered,
+		/// The attester pool has reached `MaxAttesterPool` members.
+		AttesterPoolFull,
+		/// The caller is not the author of the buffered post.
+		NotAuthor,
+		/// The buffered post's reveal window has not elapsed yet.
+		StillPending,
+		/// No buffered post exists at this index; it was already promoted or rejected.
+		AlreadyFinalized,
 	}
 
 	/// The pallet's dispatchable functions ([`Call`]s).
@@ -386,58 +680,869 @@ pub mod pallet {
 	/// The [`weight`] macro is used to assign a weight to each call.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// An example dispatchable that takes a single u32 value as a parameter, writes the value
-		/// to storage and emits an event.
+		/// Create a new board.
 		///
-		/// It checks that the _origin_ for this call is _Signed_ and returns a dispatch
-		/// error if it isn't. Learn more about origins here: <https://docs.substrate.io/build/origins/>
+		/// Any signed account may create a board. `posts_per_thread` fixes the ring-buffer
+		/// capacity used by every thread on the board: once a thread has that many posts, the
+		/// oldest post slot is overwritten by the next one.
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::do_something())]
-		pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResult {
-			// Check that the extrinsic was signed and get the signer.
+		#[pallet::weight(T::WeightInfo::create_board())]
+		pub fn create_board(
+			origin: OriginFor<T>,
+			name: Vec<u8>,
+			description: Vec<u8>,
+			rules: Vec<u8>,
+			posts_per_thread: PostIndex,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			// Update storage.
-			Something::<T>::put(something);
+			ensure!(posts_per_thread > 0, Error::<T>::ThreadFull);
+
+			let name: BoundedVec<u8, MaxNameLength<T>> =
+				name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+			let description: BoundedVec<u8, MaxDescLength<T>> =
+				description.try_into().map_err(|_| Error::<T>::DescTooLong)?;
+			let rules: BoundedVec<u8, MaxRulesLength<T>> =
+				rules.try_into().map_err(|_| Error::<T>::RulesTooLong)?;
+
+			let board_index = NextBoardIndex::<T>::get();
+			let next_board_index =
+				board_index.checked_add(1).ok_or(Error::<T>::TooManyBoards)?;
+			NextBoardIndex::<T>::put(next_board_index);
+
+			Board::<T>::insert(
+				board_index,
+				BoardMetadata::<T> {
+					name,
+					description,
+					rules,
+					number_of_threads: 0,
+					posts_per_thread,
+				},
+			);
 
-			// Emit an event.
-			Self::deposit_event(Event::SomethingStored { something, who });
+			Self::deposit_event(Event::BoardCreated { board_index, who });
 
-			// Return a successful `DispatchResult`
 			Ok(())
 		}
 
-		/// An example dispatchable that may throw a custom error.
+		/// Create a new thread on a board, seeded with its first post.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::create_thread())]
+		pub fn create_thread(
+			origin: OriginFor<T>,
+			board_index: BoardIndex,
+			first_post_cid: Cid,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut board = Board::<T>::get(board_index).ok_or(Error::<T>::BoardNotFound)?;
+			let thread_index = board.number_of_threads;
+			board.number_of_threads =
+				thread_index.checked_add(1).ok_or(Error::<T>::TooManyThreads)?;
+			Board::<T>::insert(board_index, &board);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut thread = ThreadMetadata::<T> { bump_time: now, post_count: 0, next_slot: 0 };
+			let post_data = PostData::<T> { cid: first_post_cid, author: who.clone(), created_at: now };
+			Self::insert_post_into_thread(board_index, thread_index, &board, &mut thread, post_data);
+			Thread::<T>::insert(board_index, thread_index, thread);
+
+			Self::deposit_event(Event::ThreadCreated { board_index, thread_index, who });
+
+			Ok(())
+		}
+
+		/// Submit a post to a thread.
 		///
-		/// It checks that the caller is a signed origin and reads the current value from the
-		/// `Something` storage item. If a current value exists, it is incremented by 1 and then
-		/// written back to storage.
+		/// The post is not written directly into `Post` storage. It is appended to the board's
+		/// post buffer, where it awaits a data-availability attestation before it can be
+		/// promoted into a permanent post.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::submit_post())]
+		pub fn submit_post(
+			origin: OriginFor<T>,
+			board_index: BoardIndex,
+			thread_index: ThreadIndex,
+			cid: Cid,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Board::<T>::contains_key(board_index), Error::<T>::BoardNotFound);
+			ensure!(Thread::<T>::contains_key(board_index, thread_index), Error::<T>::ThreadNotFound);
+
+			let buffer_index = BufferHead::<T>::get(board_index);
+			let next_buffer_index =
+				buffer_index.checked_add(1).ok_or(Error::<T>::BufferOverflow)?;
+			BufferHead::<T>::insert(board_index, next_buffer_index);
+
+			let deposit = T::PostDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+
+			let shard = Self::shard_for_cid(&cid);
+			let now = frame_system::Pallet::<T>::block_number();
+			BufferedPosts::<T>::insert(
+				board_index,
+				buffer_index,
+				BufferedPost::<T> {
+					data: PostData::<T> { cid, author: who.clone(), created_at: now },
+					board_index,
+					thread_index,
+					shard,
+					deposit,
+				},
+			);
+
+			Self::deposit_event(Event::PostBuffered { board_index, thread_index, buffer_index, who });
+
+			Ok(())
+		}
+
+		/// Commit to a vote on a buffered post's availability.
 		///
-		/// ## Errors
+		/// The first commit from an attester is recorded as `FirstCommit`; a second commit is
+		/// recorded as `SecondCommit`, keeping both hashes around until reveal. Only
+		/// accepted within the attestation's `CommitWindow`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::commit_attestation())]
+		pub fn commit_attestation(
+			origin: OriginFor<T>,
+			board: BoardIndex,
+			buffer_index: BufferIndex,
+			shard: ShardIndex,
+			commitment: H256,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_commit(board, buffer_index, shard, who, commitment)
+		}
+
+		/// Reveal a previously committed vote on a buffered post's availability.
 		///
-		/// The function will return an error under the following conditions:
+		/// The reveal is only accepted once the commit window has closed and while the
+		/// subsequent `RevealWindow` is still open. If the revealed `(vote, salt)` does not hash
+		/// to the stored commitment, the vote is recorded as `Invalid` rather than rejected
+		/// outright, so dishonest attesters can still be tallied as faulty.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::reveal_attestation())]
+		pub fn reveal_attestation(
+			origin: OriginFor<T>,
+			board: BoardIndex,
+			buffer_index: BufferIndex,
+			shard: ShardIndex,
+			vote: Vote,
+			salt: [u8; 32],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_reveal(board, buffer_index, shard, who, vote, salt)
+		}
+
+		/// Submit an attestation commit signed by an offchain-worker attester key, as an unsigned
+		/// transaction carrying a signed payload. Used by [`Pallet::offchain_worker`] so attester
+		/// nodes can participate without a manually-submitted [`Self::commit_attestation`].
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::commit_attestation())]
+		pub fn submit_commit_unsigned_with_signed_payload(
+			origin: OriginFor<T>,
+			payload: CommitPayload<T>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			let who = payload.public.clone().into_account();
+			Self::do_commit(payload.board, payload.buffer_index, payload.shard, who, payload.commitment)
+		}
+
+		/// Submit an attestation reveal signed by an offchain-worker attester key, as an unsigned
+		/// transaction carrying a signed payload. Used by [`Pallet::offchain_worker`] so attester
+		/// nodes can participate without a manually-submitted [`Self::reveal_attestation`].
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::reveal_attestation())]
+		pub fn submit_reveal_unsigned_with_signed_payload(
+			origin: OriginFor<T>,
+			payload: RevealPayload<T>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			let who = payload.public.clone().into_account();
+			Self::do_reveal(payload.board, payload.buffer_index, payload.shard, who, payload.vote, payload.salt)
+		}
+
+		/// Join the attester pool, reserving `AttesterBond`.
 		///
-		/// - If no value has been set ([`Error::NoneValue`])
-		/// - If incrementing the value in storage causes an arithmetic overflow
-		///   ([`Error::StorageOverflow`])
-		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::cause_error())]
-		pub fn cause_error(origin: OriginFor<T>) -> DispatchResult {
-			let _who = ensure_signed(origin)?;
-
-			// Read a value from storage.
-			match Something::<T>::get() {
-				// Return an error if the value has not been set.
-				None => Err(Error::<T>::NoneValue.into()),
-				Some(old) => {
-					// Increment the value read from storage. This will cause an error in the event
-					// of overflow.
-					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
-					// Update the value in storage with the incremented result.
-					Something::<T>::put(new);
-					Ok(())
+		/// Registered accounts are eligible to be elected into a shard's attester set at the next
+		/// election in [`Pallet::on_initialize`]; registering does not itself grant a seat.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::register_attester())]
+		pub fn register_attester(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			AttesterPool::<T>::try_mutate(|pool| -> DispatchResult {
+				ensure!(!pool.iter().any(|a| a == &who), Error::<T>::AlreadyRegistered);
+				pool.try_push(who.clone()).map_err(|_| Error::<T>::AttesterPoolFull)?;
+				Ok(())
+			})?;
+
+			let bond = T::AttesterBond::get();
+			T::Currency::reserve(&who, bond)?;
+			AttesterBonds::<T>::insert(&who, bond);
+
+			Self::deposit_event(Event::AttesterRegistered { who });
+
+			Ok(())
+		}
+
+		/// Leave the attester pool, unreserving the attester's recorded bond.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::unregister_attester())]
+		pub fn unregister_attester(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			AttesterPool::<T>::try_mutate(|pool| -> DispatchResult {
+				let index = pool.iter().position(|a| a == &who).ok_or(Error::<T>::NotRegistered)?;
+				pool.remove(index);
+				Ok(())
+			})?;
+
+			let bond = AttesterBonds::<T>::take(&who).unwrap_or_else(T::AttesterBond::get);
+			T::Currency::unreserve(&who, bond);
+
+			Self::deposit_event(Event::AttesterUnregistered { who });
+
+			Ok(())
+		}
+
+		/// Reclaim a buffered post that never reached finalization.
+		///
+		/// A buffered post whose shard had no elected attesters, or whose attesters never
+		/// committed, has no `Attestations` entry, so [`Pallet::try_finalize_post`] deliberately
+		/// leaves it in `BufferedPosts` past its reveal deadline instead of rejecting and slashing
+		/// it. Rather than leave the author's `PostDeposit` reserved forever in that case, the
+		/// post's author may reclaim it once the commit and reveal windows have both fully
+		/// elapsed (measured from the post's buffering time if no attestation was ever started),
+		/// purging the stale entry from `BufferedPosts`/`Attestations` and unreserving the
+		/// deposit.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::reclaim_buffered_post())]
+		pub fn reclaim_buffered_post(
+			origin: OriginFor<T>,
+			board: BoardIndex,
+			buffer_index: BufferIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let post =
+				BufferedPosts::<T>::get(board, buffer_index).ok_or(Error::<T>::AlreadyFinalized)?;
+			ensure!(post.data.author == who, Error::<T>::NotAuthor);
+
+			let attestation = Attestations::<T>::get((board, buffer_index, post.shard));
+			let created_at = attestation.as_ref().map_or(post.data.created_at, |a| a.created_at);
+			let reveal_deadline = created_at
+				.saturating_add(T::CommitWindow::get())
+				.saturating_add(T::RevealWindow::get());
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now > reveal_deadline, Error::<T>::StillPending);
+
+			T::Currency::unreserve(&who, post.deposit);
+
+			BufferedPosts::<T>::remove(board, buffer_index);
+			Attestations::<T>::remove((board, buffer_index, post.shard));
+
+			Self::deposit_event(Event::BufferedPostReclaimed { board_index: board, buffer_index });
+
+			Ok(())
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Once every `ShardEpochLength` blocks, re-elect every board's shard attester sets from
+		/// the registered attester pool. See [`Pallet::elect_shard_attesters`].
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			if T::ShardEpochLength::get().is_zero() || !(now % T::ShardEpochLength::get()).is_zero() {
+				return Weight::zero();
+			}
+			Self::elect_shard_attesters(now);
+			Weight::zero()
+		}
+
+		/// Fetch each buffered post's content by `Cid` and auto-attest its availability.
+		///
+		/// For every shard this node holds an attester key for, the worker walks that board's
+		/// buffered posts, resolves each `Cid` over offchain HTTP/IPFS, and submits a commit (or,
+		/// once the reveal window is open, a reveal) as an unsigned transaction with a signed
+		/// payload. See [`Self::run_offchain_worker`].
+		fn offchain_worker(block_number: BlockNumberFor<T>) {
+			if let Err(e) = Self::run_offchain_worker(block_number) {
+				log::warn!(target: "board-state", "offchain worker skipped a round: {:?}", e);
+			}
+		}
+
+		/// Spend any weight left over after the block's extrinsics finalizing buffered posts
+		/// whose reveal window has closed, resuming from [`FinalizationCursor`] so the work is
+		/// spread across blocks. See [`Pallet::process_finalizations`].
+		fn on_idle(_now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::process_finalizations(remaining_weight)
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		/// Only admit unsigned commit/reveal transactions whose signed payload is genuinely
+		/// signed by the claimed public key, and whose signer is a member of the targeted
+		/// shard's attester set.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (board, buffer_index, shard, public, tag) = match call {
+				Call::submit_commit_unsigned_with_signed_payload { payload, signature } => {
+					let signature_valid =
+						SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone());
+					if !signature_valid {
+						return InvalidTransaction::BadProof.into();
+					}
+					(payload.board, payload.buffer_index, payload.shard, payload.public.clone(), b"commit".to_vec())
+				},
+				Call::submit_reveal_unsigned_with_signed_payload { payload, signature } => {
+					let signature_valid =
+						SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone());
+					if !signature_valid {
+						return InvalidTransaction::BadProof.into();
+					}
+					(payload.board, payload.buffer_index, payload.shard, payload.public.clone(), b"reveal".to_vec())
+				},
+				_ => return InvalidTransaction::Call.into(),
+			};
+
+			let who = public.into_account();
+			// Once an attestation exists its committee is pinned (see `AttestationData::committee`),
+			// so a signer must be validated against that snapshot rather than the live, possibly
+			// re-elected `ShardAttesters` set.
+			let committee = match Attestations::<T>::get((board, buffer_index, shard)) {
+				Some(attestation) => attestation.committee,
+				None => ShardAttesters::<T>::get(board, shard).ok_or(InvalidTransaction::Stale)?,
+			};
+			if !committee.iter().any(|a| a == &who) {
+				return InvalidTransaction::BadSigner.into();
+			}
+
+			ValidTransaction::with_tag_prefix("BoardStateAttestation")
+				.priority(TransactionPriority::max_value())
+				.and_provides((tag, board, buffer_index, shard, who))
+				.longevity(5)
+				.propagate(true)
+				.build()
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Write `post_data` into `thread`'s ring buffer of `board.posts_per_thread` slots,
+		/// overwriting the oldest slot once the thread is at capacity, and bump the thread.
+		fn insert_post_into_thread(
+			board_index: BoardIndex,
+			thread_index: ThreadIndex,
+			board: &BoardMetadata<T>,
+			thread: &mut ThreadMetadata<T>,
+			post_data: PostData<T>,
+		) {
+			let slot = thread.next_slot;
+			Post::<T>::insert((board_index, thread_index, slot), post_data);
+
+			thread.next_slot = (slot + 1) % board.posts_per_thread;
+			if thread.post_count < board.posts_per_thread {
+				thread.post_count += 1;
+			}
+			thread.bump_time = frame_system::Pallet::<T>::block_number();
+		}
+
+		/// Build a fresh `AttestationData` pinned to `committee`, with every member still `Pending`.
+		fn new_attestation_data(
+			created_at: BlockNumberFor<T>,
+			committee: Attesters<T>,
+		) -> AttestationData<T> {
+			let votes = BoundedVec::try_from(vec![AttestationState::Pending; committee.len()])
+				.unwrap_or_default();
+			AttestationData::<T> { created_at, committee, votes }
+		}
+
+		/// Shared commit logic used by both the signed [`Call::commit_attestation`] and the
+		/// offchain-worker-submitted [`Call::submit_commit_unsigned_with_signed_payload`].
+		fn do_commit(
+			board: BoardIndex,
+			buffer_index: BufferIndex,
+			shard: ShardIndex,
+			who: T::AccountId,
+			commitment: H256,
+		) -> DispatchResult {
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut attestation_data = match Attestations::<T>::get((board, buffer_index, shard)) {
+				Some(existing) => existing,
+				None => {
+					let post = BufferedPosts::<T>::get(board, buffer_index)
+						.ok_or(Error::<T>::AlreadyFinalized)?;
+					let committee =
+						ShardAttesters::<T>::get(board, shard).ok_or(Error::<T>::ShardNotFound)?;
+					Self::new_attestation_data(post.data.created_at, committee)
+				},
+			};
+			let index = attestation_data
+				.committee
+				.iter()
+				.position(|a| a == &who)
+				.ok_or(Error::<T>::NotAttester)?;
+
+			ensure!(
+				now <= attestation_data.created_at.saturating_add(T::CommitWindow::get()),
+				Error::<T>::CommitWindowClosed
+			);
+
+			attestation_data.votes[index] = match attestation_data.votes[index].clone() {
+				AttestationState::Pending => AttestationState::FirstCommit(commitment),
+				AttestationState::FirstCommit(first) => AttestationState::SecondCommit(first, commitment),
+				AttestationState::SecondCommit(..) | AttestationState::Revealed(_) =>
+					return Err(Error::<T>::AlreadyCommitted.into()),
+			};
+
+			Attestations::<T>::insert((board, buffer_index, shard), attestation_data);
+
+			Self::deposit_event(Event::AttestationCommitted { board_index: board, buffer_index, shard, who });
+
+			Ok(())
+		}
+
+		/// Shared reveal logic used by both the signed [`Call::reveal_attestation`] and the
+		/// offchain-worker-submitted [`Call::submit_reveal_unsigned_with_signed_payload`].
+		fn do_reveal(
+			board: BoardIndex,
+			buffer_index: BufferIndex,
+			shard: ShardIndex,
+			who: T::AccountId,
+			vote: Vote,
+			salt: [u8; 32],
+		) -> DispatchResult {
+			let mut attestation_data =
+				Attestations::<T>::get((board, buffer_index, shard)).ok_or(Error::<T>::NoCommitment)?;
+			let index = attestation_data
+				.committee
+				.iter()
+				.position(|a| a == &who)
+				.ok_or(Error::<T>::NotAttester)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let commit_deadline = attestation_data.created_at.saturating_add(T::CommitWindow::get());
+			let reveal_deadline = commit_deadline.saturating_add(T::RevealWindow::get());
+			ensure!(now > commit_deadline, Error::<T>::RevealWindowNotOpen);
+			ensure!(now <= reveal_deadline, Error::<T>::RevealWindowClosed);
+
+			let commitment = match attestation_data.votes[index] {
+				AttestationState::FirstCommit(commitment) | AttestationState::SecondCommit(_, commitment) =>
+					commitment,
+				AttestationState::Pending | AttestationState::Revealed(_) =>
+					return Err(Error::<T>::NoCommitment.into()),
+			};
+
+			let expected = H256::from(blake2_256(&(vote.clone(), salt, who.clone(), buffer_index).encode()));
+			let revealed_vote = if expected == commitment {
+				match vote {
+					Vote::True => RevealedVote::Aye,
+					Vote::False => RevealedVote::Nay,
+				}
+			} else {
+				RevealedVote::Invalid
+			};
+
+			attestation_data.votes[index] = AttestationState::Revealed(revealed_vote.clone());
+			Attestations::<T>::insert((board, buffer_index, shard), attestation_data);
+
+			Self::deposit_event(Event::AttestationRevealed {
+				board_index: board,
+				buffer_index,
+				shard,
+				who,
+				vote: revealed_vote,
+			});
+
+			Ok(())
+		}
+
+		/// Drive the self-attestation loop: for every buffered post whose assigned shard this node
+		/// holds an attester key for, submit a commit or reveal.
+		fn run_offchain_worker(_block_number: BlockNumberFor<T>) -> Result<(), &'static str> {
+			let signer = Signer::<T, T::AuthorityId>::all_accounts();
+			if !signer.can_sign() {
+				return Err("no local attester keys in the keystore for this pallet's KEY_TYPE");
+			}
+
+			let local_accounts = Self::local_attester_accounts();
+
+			for (board, buffer_index, buffered_post) in BufferedPosts::<T>::iter() {
+				let shard = buffered_post.shard;
+
+				// Skip the (expensive) offchain fetch entirely unless one of this node's local
+				// keys is actually a member of the shard's attester set, rather than relying on
+				// `validate_unsigned` to drop the submission after the fact.
+				let is_local_member = ShardAttesters::<T>::get(board, shard)
+					.is_some_and(|attesters| attesters.iter().any(|a| local_accounts.contains(a)));
+				if !is_local_member {
+					continue;
+				}
+
+				let Some(attestation_data) = Attestations::<T>::get((board, buffer_index, shard))
+				else {
+					Self::attest_one(&signer, board, buffer_index, shard, &buffered_post.data.cid);
+					continue;
+				};
+
+				let now = frame_system::Pallet::<T>::block_number();
+				let commit_deadline = attestation_data.created_at.saturating_add(T::CommitWindow::get());
+				let reveal_deadline = commit_deadline.saturating_add(T::RevealWindow::get());
+
+				if now <= commit_deadline {
+					Self::attest_one(&signer, board, buffer_index, shard, &buffered_post.data.cid);
+				} else if now <= reveal_deadline {
+					Self::reveal_one(&signer, board, buffer_index, shard);
+				}
+			}
+
+			Ok(())
+		}
+
+		/// The `AccountId`s this node holds an offchain-worker signing key for, derived from the
+		/// local keystore so callers can test shard membership before doing any per-post work.
+		fn local_attester_accounts() -> Vec<T::AccountId> {
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all()
+				.into_iter()
+				.map(|key| {
+					let generic_public =
+						<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key);
+					let public: T::Public = generic_public.into();
+					public.into_account()
+				})
+				.collect()
+		}
+
+		/// Fetch `cid`'s content offchain, decide availability, and submit a commit — persisting
+		/// the vote and salt locally so the later reveal pass can reproduce the same commitment.
+		///
+		/// Commits exactly once per `(board, buffer_index, shard)`: if a vote is already
+		/// persisted locally, it's reused as-is rather than minting a fresh salt on every call,
+		/// which would otherwise desync the locally stored salt from whichever commitment this
+		/// node already has on chain and make the later reveal fail to match it.
+		fn attest_one(
+			signer: &Signer<T, T::AuthorityId, frame_system::offchain::ForAll>,
+			board: BoardIndex,
+			buffer_index: BufferIndex,
+			shard: ShardIndex,
+			cid: &Cid,
+		) {
+			if Self::local_vote(board, buffer_index, shard).is_some() {
+				return;
+			}
+
+			let vote = if Self::fetch_cid_is_available(cid) { Vote::True } else { Vote::False };
+			let salt = sp_io::offchain::random_seed();
+			Self::store_local_vote(board, buffer_index, shard, &vote, &salt);
+
+			let commitment_inputs = |public: &T::Public| {
+				let who = public.clone().into_account();
+				H256::from(blake2_256(&(vote.clone(), salt, who, buffer_index).encode()))
+			};
+
+			let _ = signer.send_unsigned_transaction(
+				|account| CommitPayload {
+					board,
+					buffer_index,
+					shard,
+					commitment: commitment_inputs(&account.public),
+					public: account.public.clone(),
 				},
+				|payload, signature| Call::submit_commit_unsigned_with_signed_payload { payload, signature },
+			);
+		}
+
+		/// Submit the reveal matching the vote and salt persisted by [`Self::attest_one`].
+		fn reveal_one(
+			signer: &Signer<T, T::AuthorityId, frame_system::offchain::ForAll>,
+			board: BoardIndex,
+			buffer_index: BufferIndex,
+			shard: ShardIndex,
+		) {
+			let Some((vote, salt)) = Self::local_vote(board, buffer_index, shard) else { return };
+
+			let _ = signer.send_unsigned_transaction(
+				|account| RevealPayload {
+					board,
+					buffer_index,
+					shard,
+					vote: vote.clone(),
+					salt,
+					public: account.public.clone(),
+				},
+				|payload, signature| Call::submit_reveal_unsigned_with_signed_payload { payload, signature },
+			);
+		}
+
+		/// Resolve `cid`'s content over offchain HTTP/IPFS within a short timeout, treating a
+		/// `200 OK` response as available and anything else (including a timeout) as unavailable.
+		fn fetch_cid_is_available(cid: &Cid) -> bool {
+			const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+			let mut url = sp_std::vec::Vec::from(&b"https://ipfs.io/ipfs/"[..]);
+			for byte in cid.as_bytes() {
+				url.push(HEX_DIGITS[(byte >> 4) as usize]);
+				url.push(HEX_DIGITS[(byte & 0x0f) as usize]);
+			}
+			let Ok(url) = sp_std::str::from_utf8(&url) else { return false };
+
+			let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(3_000));
+			let request = http::Request::get(url);
+			let Ok(pending) = request.deadline(deadline).send() else { return false };
+			let Ok(response) = pending.try_wait(deadline).and_then(|r| r.map_err(|_| http::Error::Unknown))
+			else {
+				return false;
+			};
+			response.code == 200
+		}
+
+		/// Persist `(vote, salt)` in offchain local storage so the reveal pass, run in a later
+		/// block, can reproduce the exact commitment submitted during the commit pass.
+		fn store_local_vote(
+			board: BoardIndex,
+			buffer_index: BufferIndex,
+			shard: ShardIndex,
+			vote: &Vote,
+			salt: &[u8; 32],
+		) {
+			let key = (b"board-state::vote", board, buffer_index, shard).encode();
+			let storage = sp_runtime::offchain::storage::StorageValueRef::persistent(&key);
+			storage.set(&(vote.clone(), *salt));
+		}
+
+		/// Read back the `(vote, salt)` pair persisted by [`Self::store_local_vote`].
+		fn local_vote(
+			board: BoardIndex,
+			buffer_index: BufferIndex,
+			shard: ShardIndex,
+		) -> Option<(Vote, [u8; 32])> {
+			let key = (b"board-state::vote", board, buffer_index, shard).encode();
+			let storage = sp_runtime::offchain::storage::StorageValueRef::persistent(&key);
+			storage.get::<(Vote, [u8; 32])>().ok().flatten()
+		}
+
+		/// Deterministically assign a `Cid` to a shard in `0..T::NumShards`, combining it with the
+		/// on-chain randomness available at submission time so posters cannot target a shard.
+		fn shard_for_cid(cid: &Cid) -> ShardIndex {
+			let num_shards = T::NumShards::get();
+			if num_shards == 0 {
+				return 0;
+			}
+			let (random_seed, _) = T::Randomness::random(b"board-state::post-shard");
+			let mut bytes = cid.encode();
+			bytes.extend_from_slice(random_seed.as_ref());
+			let hash = blake2_256(&bytes);
+			let value = u32::from_le_bytes([hash[0], hash[1], hash[2], hash[3]]);
+			(value % num_shards as u32) as ShardIndex
+		}
+
+		/// Re-elect every board's shard attester sets from the registered attester pool.
+		///
+		/// For each `(board, shard)` pair, the pool is Fisher-Yates shuffled using a seed drawn
+		/// from `T::Randomness` for that board, shard and epoch, and the first `AttesterSetSize`
+		/// entries of the shuffle become the shard's new attester set.
+		fn elect_shard_attesters(epoch: BlockNumberFor<T>) {
+			let pool = AttesterPool::<T>::get();
+			if pool.is_empty() {
+				return;
+			}
+
+			for (board_index, _) in Board::<T>::iter() {
+				for shard in 0..T::NumShards::get() {
+					let (seed, _) = T::Randomness::random(&(board_index, shard, epoch).encode());
+					let shuffled = Self::fisher_yates_shuffle(pool.len(), seed);
+
+					let elected = shuffled
+						.into_iter()
+						.take(T::AttesterSetSize::get() as usize)
+						.map(|index| pool[index].clone())
+						.collect::<Vec<_>>();
+					let elected: Attesters<T> = BoundedVec::try_from(elected).unwrap_or_default();
+
+					ShardAttesters::<T>::insert(board_index, shard, elected);
+					Self::deposit_event(Event::ShardAttestersElected { board_index, shard });
+				}
+			}
+		}
+
+		/// Fisher-Yates shuffle of the indices `0..len`, deriving each swap's random index from
+		/// `seed` and the current position so the whole shuffle is reproducible from one seed.
+		fn fisher_yates_shuffle(len: usize, seed: T::Hash) -> Vec<usize> {
+			let mut indices: Vec<usize> = (0..len).collect();
+			let mut i = len;
+			while i > 1 {
+				i -= 1;
+				let digest = blake2_256(&(seed, i as u64).encode());
+				let random = u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]]) as usize;
+				indices.swap(i, random % (i + 1));
+			}
+			indices
+		}
+
+		/// Scan up to `MaxFinalizationsPerBlock` buffered posts, starting from
+		/// [`FinalizationCursor`], finalizing any whose reveal window has closed, and save the
+		/// cursor so the next call resumes where this one left off.
+		fn process_finalizations(remaining_weight: Weight) -> Weight {
+			let per_item = T::DbWeight::get().reads_writes(4, 4);
+			let max_items = T::MaxFinalizationsPerBlock::get();
+
+			let mut consumed = Weight::zero();
+			let mut scanned = 0u32;
+			let mut cursor = FinalizationCursor::<T>::get();
+
+			loop {
+				if scanned >= max_items || !consumed.saturating_add(per_item).all_lte(remaining_weight) {
+					break;
+				}
+
+				let mut iter = if cursor.is_empty() {
+					BufferedPosts::<T>::iter()
+				} else {
+					BufferedPosts::<T>::iter_from(cursor.clone())
+				};
+				let Some((board, buffer_index, post)) = iter.next() else {
+					// Reached the end of the map; wrap around to the start next pass.
+					cursor = Vec::new();
+					break;
+				};
+				cursor = iter.last_raw_key().to_vec();
+
+				if Self::try_finalize_post(board, buffer_index, &post) {
+					consumed = consumed.saturating_add(per_item);
+				}
+				scanned += 1;
+			}
+
+			FinalizationCursor::<T>::put(cursor);
+			consumed
+		}
+
+		/// Finalize a single buffered post if its reveal window has closed: promote it into
+		/// permanent storage if its shard's `Aye` votes meet `AvailabilityThreshold`, otherwise
+		/// drop it. Returns `false` if the post's reveal window is still open, or if no attester
+		/// ever committed a vote for it (the shard had no committee able to attest at all, so the
+		/// post is left buffered for [`Pallet::reclaim_buffered_post`] rather than being rejected
+		/// and having its author's deposit slashed for the chain's own election failure).
+		fn try_finalize_post(
+			board: BoardIndex,
+			buffer_index: BufferIndex,
+			post: &BufferedPost<T>,
+		) -> bool {
+			let attestation = Attestations::<T>::get((board, buffer_index, post.shard));
+			let created_at = attestation.as_ref().map_or(post.data.created_at, |a| a.created_at);
+			let reveal_deadline = created_at
+				.saturating_add(T::CommitWindow::get())
+				.saturating_add(T::RevealWindow::get());
+
+			let now = frame_system::Pallet::<T>::block_number();
+			if now <= reveal_deadline {
+				return false;
+			}
+
+			let Some(attestation) = attestation else {
+				return false;
+			};
+
+			let aye_votes = attestation
+				.votes
+				.iter()
+				.filter(|v| matches!(v, AttestationState::Revealed(RevealedVote::Aye)))
+				.count() as u32;
+
+			if T::AvailabilityThreshold::get().mul_floor(T::AttesterSetSize::get()) <= aye_votes {
+				T::Currency::unreserve(&post.data.author, post.deposit);
+				Self::promote_buffered_post(board, buffer_index, post);
+			} else {
+				let _ = T::Currency::slash_reserved(&post.data.author, post.deposit);
+				Self::deposit_event(Event::PostRejected {
+					board_index: board,
+					thread_index: post.thread_index,
+					buffer_index,
+				});
+			}
+
+			Self::slash_and_reward_attesters(&attestation);
+
+			BufferedPosts::<T>::remove(board, buffer_index);
+			Attestations::<T>::remove((board, buffer_index, post.shard));
+
+			true
+		}
+
+		/// Write a buffered post into its thread's ring buffer and emit [`Event::PostFinalized`].
+		fn promote_buffered_post(board: BoardIndex, buffer_index: BufferIndex, post: &BufferedPost<T>) {
+			let (Some(board_meta), Some(mut thread)) =
+				(Board::<T>::get(board), Thread::<T>::get(board, post.thread_index))
+			else {
+				return;
+			};
+
+			Self::insert_post_into_thread(
+				board,
+				post.thread_index,
+				&board_meta,
+				&mut thread,
+				post.data.clone(),
+			);
+			Thread::<T>::insert(board, post.thread_index, thread);
+
+			Self::deposit_event(Event::PostFinalized {
+				board_index: board,
+				thread_index: post.thread_index,
+				buffer_index,
+			});
+		}
+
+		/// Slash a portion of each attester's recorded `AttesterBonds` bond if they revealed
+		/// `Invalid` or never revealed at all, and split the slashed funds evenly as a reward
+		/// among attesters who revealed `Aye` or `Nay`, paid out of the slashed imbalance itself
+		/// rather than minted. Uses `attestation.committee`, the attester set pinned when this
+		/// attestation was created, rather than the live `ShardAttesters`, so a re-election that
+		/// happened mid-attestation can't pair `votes` against the wrong accounts.
+		fn slash_and_reward_attesters(attestation: &AttestationData<T>) {
+			let mut pot: Option<NegativeImbalanceOf<T>> = None;
+			let mut honest = Vec::new();
+
+			for (attester, vote) in attestation.committee.iter().zip(attestation.votes.iter()) {
+				match vote {
+					AttestationState::Revealed(RevealedVote::Aye) |
+					AttestationState::Revealed(RevealedVote::Nay) => honest.push(attester.clone()),
+					AttestationState::Revealed(RevealedVote::Invalid) |
+					AttestationState::Pending |
+					AttestationState::FirstCommit(_) |
+					AttestationState::SecondCommit(..) => {
+						let bond = AttesterBonds::<T>::get(attester).unwrap_or_else(T::AttesterBond::get);
+						let slash_amount = T::AttesterSlashFraction::get().mul_floor(bond);
+						let (imbalance, _) = T::Currency::slash_reserved(attester, slash_amount);
+						pot = Some(match pot {
+							Some(existing) => existing.merge(imbalance),
+							None => imbalance,
+						});
+					},
+				}
+			}
+
+			let Some(mut pot) = pot else { return };
+			if honest.is_empty() {
+				// No honest attester to pay; let the slash stand rather than minting it back to
+				// no one.
+				drop(pot);
+				return;
+			}
+
+			let share = pot.peek() / (honest.len() as u32).into();
+			for attester in &honest[..honest.len() - 1] {
+				let (piece, rest) = pot.split(share);
+				T::Currency::resolve_creating(attester, piece);
+				pot = rest;
 			}
+			T::Currency::resolve_creating(&honest[honest.len() - 1], pot);
 		}
 	}
 }
@@ -0,0 +1,151 @@
+//! A minimal mock runtime used to unit test this pallet.
+
+use crate as pallet_board_state;
+use frame_support::traits::{ConstU32, ConstU64, ConstU8};
+use sp_core::{sr25519::Signature, H256};
+use sp_runtime::{
+	testing::TestXt,
+	traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Randomness, Verify},
+	BuildStorage, MultiSigner, Percent,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+type Balance = u64;
+type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		BoardState: pallet_board_state,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+	type RuntimeTask = ();
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = Balance;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		_public: Self::Public,
+		_account: Self::AccountId,
+		nonce: Self::Nonce,
+	) -> Option<(RuntimeCall, <Self::Extrinsic as sp_runtime::traits::Extrinsic>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+	RuntimeCall: From<C>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = TestXt<RuntimeCall, ()>;
+}
+
+frame_support::parameter_types! {
+	pub const AvailabilityThresholdGet: Percent = Percent::from_percent(50);
+	pub const AttesterSlashFractionGet: Percent = Percent::from_percent(10);
+}
+
+/// Pseudo-randomness source for tests: deterministic but varies with the current block number, so
+/// shard assignment/election exercises different outcomes across the blocks a test advances.
+pub struct TestRandomness;
+impl Randomness<H256, u64> for TestRandomness {
+	fn random(subject: &[u8]) -> (H256, u64) {
+		let block_number = System::block_number();
+		let mut bytes = subject.to_vec();
+		bytes.extend_from_slice(&block_number.to_le_bytes());
+		(H256::from(sp_io::hashing::blake2_256(&bytes)), block_number)
+	}
+}
+
+impl pallet_board_state::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type AuthorityId = pallet_board_state::crypto::AttesterAuthId;
+	type MaxNameLength = ConstU32<32>;
+	type MaxDescLength = ConstU32<256>;
+	type MaxRulesLength = ConstU32<256>;
+	type AttesterSetSize = ConstU32<3>;
+	type CommitWindow = ConstU64<5>;
+	type RevealWindow = ConstU64<5>;
+	type Randomness = TestRandomness;
+	type MaxAttesterPool = ConstU32<10>;
+	type NumShards = ConstU8<2>;
+	// Long enough that no test inadvertently crosses an election boundary and reshuffles a
+	// shard's attester set mid-flow; tests that want an election call `on_initialize` directly.
+	type ShardEpochLength = ConstU64<1_000>;
+	type Currency = Balances;
+	type AttesterBond = ConstU64<100>;
+	type AvailabilityThreshold = AvailabilityThresholdGet;
+	type MaxFinalizationsPerBlock = ConstU32<10>;
+	type PostDeposit = ConstU64<10>;
+	type AttesterSlashFraction = AttesterSlashFractionGet;
+}
+
+/// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: (1..=10u64).map(|i| (account(i), 1_000)).collect(),
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	t.into()
+}
+
+/// Deterministically derive a test account from a small integer, mirroring the convention used
+/// throughout `tests.rs`.
+pub fn account(n: u64) -> AccountId {
+	MultiSigner::from(sp_core::sr25519::Public::from_raw([n as u8; 32])).into_account()
+}
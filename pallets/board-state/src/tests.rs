@@ -0,0 +1,440 @@
+//! Unit tests for the board-state pallet.
+
+use crate::{
+	mock::{account, new_test_ext, Balances, BoardState, RuntimeOrigin, System, Test},
+	BufferedPosts, Error, Event, ShardAttesters, Vote,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Currency, Hooks, ReservableCurrency},
+};
+use parity_scale_codec::Encode;
+use sp_core::H256;
+use sp_io::hashing::blake2_256;
+
+const COMMIT_WINDOW: u64 = 5;
+const REVEAL_WINDOW: u64 = 5;
+
+/// Advance the chain to block `n`, firing `on_initialize`/`on_idle` along the way just like a
+/// real block production loop would.
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		BoardState::on_initialize(next);
+		BoardState::on_idle(next, frame_support::weights::Weight::from_parts(1_000_000_000, 0));
+	}
+}
+
+/// Reproduce the commitment hash `do_reveal` expects, so tests can commit/reveal like a real
+/// attester would.
+fn commitment_for(
+	vote: Vote,
+	salt: [u8; 32],
+	who: <Test as frame_system::Config>::AccountId,
+	buffer_index: crate::BufferIndex,
+) -> H256 {
+	H256::from(blake2_256(&(vote, salt, who, buffer_index).encode()))
+}
+
+/// Give `shard` of `board` a fixed attester set directly, bypassing the randomised election so
+/// tests can drive commit/reveal deterministically.
+fn set_shard_attesters(board: crate::BoardIndex, shard: crate::ShardIndex, attesters: Vec<u64>) {
+	let attesters: crate::Attesters<Test> =
+		attesters.into_iter().map(account).collect::<Vec<_>>().try_into().unwrap();
+	ShardAttesters::<Test>::insert(board, shard, attesters);
+}
+
+fn create_board(posts_per_thread: u16) -> crate::BoardIndex {
+	assert_ok!(BoardState::create_board(
+		RuntimeOrigin::signed(account(1)),
+		b"general".to_vec(),
+		b"general discussion".to_vec(),
+		b"be nice".to_vec(),
+		posts_per_thread,
+	));
+	0
+}
+
+fn create_thread(board: crate::BoardIndex, cid: H256) -> crate::ThreadIndex {
+	assert_ok!(BoardState::create_thread(RuntimeOrigin::signed(account(1)), board, cid));
+	0
+}
+
+#[test]
+fn ring_buffer_rotates_through_all_slots_once_full() {
+	new_test_ext().execute_with(|| {
+		let board = create_board(2);
+		let thread = create_thread(board, H256::repeat_byte(1));
+
+		set_shard_attesters(board, 0, vec![2, 3, 4]);
+		set_shard_attesters(board, 1, vec![2, 3, 4]);
+
+		// Fill the second slot.
+		buffer_commit_reveal_and_finalize(board, thread, H256::repeat_byte(2), vec![2, 3, 4]);
+		// The ring buffer is now full; this post must overwrite the oldest slot (slot 0).
+		buffer_commit_reveal_and_finalize(board, thread, H256::repeat_byte(3), vec![2, 3, 4]);
+
+		let slot_0 = crate::Post::<Test>::get((board, thread, 0)).unwrap();
+		let slot_1 = crate::Post::<Test>::get((board, thread, 1)).unwrap();
+		assert_eq!(slot_0.cid, H256::repeat_byte(3), "slot 0 should have rotated to the newest post");
+		assert_eq!(slot_1.cid, H256::repeat_byte(2), "slot 1 should still hold the second post");
+	});
+}
+
+/// Drive a single buffered post all the way through commit, reveal (all attesters vote `Aye`),
+/// and finalization so it lands in `Post` storage.
+fn buffer_commit_reveal_and_finalize(
+	board: crate::BoardIndex,
+	thread: crate::ThreadIndex,
+	cid: H256,
+	attesters: Vec<u64>,
+) {
+	assert_ok!(BoardState::submit_post(RuntimeOrigin::signed(account(1)), board, thread, cid));
+	let buffer_index = BoardState::buffer_head(board) - 1;
+	let buffered = BufferedPosts::<Test>::get(board, buffer_index).unwrap();
+	let shard = buffered.shard;
+	set_shard_attesters(board, shard, attesters.clone());
+
+	let salt = [7u8; 32];
+	for attester in &attesters {
+		let who = account(*attester);
+		let commitment = commitment_for(Vote::True, salt, who.clone(), buffer_index);
+		assert_ok!(BoardState::commit_attestation(
+			RuntimeOrigin::signed(who),
+			board,
+			buffer_index,
+			shard,
+			commitment,
+		));
+	}
+
+	run_to_block(System::block_number() + COMMIT_WINDOW + 1);
+
+	for attester in &attesters {
+		let who = account(*attester);
+		assert_ok!(BoardState::reveal_attestation(
+			RuntimeOrigin::signed(who),
+			board,
+			buffer_index,
+			shard,
+			Vote::True,
+			salt,
+		));
+	}
+
+	run_to_block(System::block_number() + REVEAL_WINDOW + 1);
+}
+
+#[test]
+fn commit_is_rejected_once_the_commit_window_has_closed() {
+	new_test_ext().execute_with(|| {
+		let board = create_board(4);
+		let thread = create_thread(board, H256::repeat_byte(1));
+
+		assert_ok!(BoardState::submit_post(
+			RuntimeOrigin::signed(account(1)),
+			board,
+			thread,
+			H256::repeat_byte(2),
+		));
+		let buffer_index = BoardState::buffer_head(board) - 1;
+		let shard = BufferedPosts::<Test>::get(board, buffer_index).unwrap().shard;
+		set_shard_attesters(board, shard, vec![2]);
+
+		run_to_block(System::block_number() + COMMIT_WINDOW + 1);
+
+		let commitment = commitment_for(Vote::True, [0u8; 32], account(2), buffer_index);
+		assert_noop!(
+			BoardState::commit_attestation(
+				RuntimeOrigin::signed(account(2)),
+				board,
+				buffer_index,
+				shard,
+				commitment,
+			),
+			Error::<Test>::CommitWindowClosed,
+		);
+	});
+}
+
+#[test]
+fn reveal_is_rejected_before_commit_window_closes_and_after_reveal_window_closes() {
+	new_test_ext().execute_with(|| {
+		let board = create_board(4);
+		let thread = create_thread(board, H256::repeat_byte(1));
+
+		assert_ok!(BoardState::submit_post(
+			RuntimeOrigin::signed(account(1)),
+			board,
+			thread,
+			H256::repeat_byte(2),
+		));
+		let buffer_index = BoardState::buffer_head(board) - 1;
+		let shard = BufferedPosts::<Test>::get(board, buffer_index).unwrap().shard;
+		set_shard_attesters(board, shard, vec![2]);
+
+		let salt = [1u8; 32];
+		let commitment = commitment_for(Vote::True, salt, account(2), buffer_index);
+		assert_ok!(BoardState::commit_attestation(
+			RuntimeOrigin::signed(account(2)),
+			board,
+			buffer_index,
+			shard,
+			commitment,
+		));
+
+		// Still inside the commit window: reveal must not be accepted yet.
+		assert_noop!(
+			BoardState::reveal_attestation(
+				RuntimeOrigin::signed(account(2)),
+				board,
+				buffer_index,
+				shard,
+				Vote::True,
+				salt,
+			),
+			Error::<Test>::RevealWindowNotOpen,
+		);
+
+		run_to_block(System::block_number() + COMMIT_WINDOW + REVEAL_WINDOW + 2);
+
+		assert_noop!(
+			BoardState::reveal_attestation(
+				RuntimeOrigin::signed(account(2)),
+				board,
+				buffer_index,
+				shard,
+				Vote::True,
+				salt,
+			),
+			Error::<Test>::RevealWindowClosed,
+		);
+	});
+}
+
+#[test]
+fn post_is_promoted_when_availability_threshold_is_met() {
+	new_test_ext().execute_with(|| {
+		let board = create_board(4);
+		let thread = create_thread(board, H256::repeat_byte(1));
+		let cid = H256::repeat_byte(2);
+		let author_balance_before = Balances::free_balance(account(1));
+
+		buffer_commit_reveal_and_finalize(board, thread, cid, vec![2, 3, 4]);
+
+		assert_eq!(crate::Post::<Test>::get((board, thread, 1)).unwrap().cid, cid);
+		// The author's PostDeposit was unreserved, not slashed.
+		assert_eq!(Balances::free_balance(account(1)), author_balance_before);
+		assert!(System::events().iter().any(|r| matches!(
+			r.event,
+			crate::mock::RuntimeEvent::BoardState(Event::PostFinalized { board_index, .. }) if board_index == board
+		)));
+	});
+}
+
+#[test]
+fn post_is_rejected_and_author_slashed_when_threshold_is_not_met() {
+	new_test_ext().execute_with(|| {
+		let board = create_board(4);
+		let thread = create_thread(board, H256::repeat_byte(1));
+		let attesters = vec![2, 3, 4];
+		let author_balance_before = Balances::free_balance(account(1));
+
+		assert_ok!(BoardState::submit_post(
+			RuntimeOrigin::signed(account(1)),
+			board,
+			thread,
+			H256::repeat_byte(9),
+		));
+		let buffer_index = BoardState::buffer_head(board) - 1;
+		let shard = BufferedPosts::<Test>::get(board, buffer_index).unwrap().shard;
+		set_shard_attesters(board, shard, attesters.clone());
+
+		let salt = [3u8; 32];
+		for attester in &attesters {
+			let who = account(*attester);
+			let commitment = commitment_for(Vote::False, salt, who.clone(), buffer_index);
+			assert_ok!(BoardState::commit_attestation(
+				RuntimeOrigin::signed(who),
+				board,
+				buffer_index,
+				shard,
+				commitment,
+			));
+		}
+		run_to_block(System::block_number() + COMMIT_WINDOW + 1);
+		for attester in &attesters {
+			let who = account(*attester);
+			assert_ok!(BoardState::reveal_attestation(
+				RuntimeOrigin::signed(who),
+				board,
+				buffer_index,
+				shard,
+				Vote::False,
+				salt,
+			));
+		}
+		run_to_block(System::block_number() + REVEAL_WINDOW + 1);
+
+		assert!(BufferedPosts::<Test>::get(board, buffer_index).is_none());
+		assert!(Balances::free_balance(account(1)) < author_balance_before, "author's deposit must be slashed");
+	});
+}
+
+#[test]
+fn under_attested_post_is_not_slashed_and_stays_reclaimable() {
+	new_test_ext().execute_with(|| {
+		let board = create_board(4);
+		let thread = create_thread(board, H256::repeat_byte(1));
+
+		assert_ok!(BoardState::submit_post(
+			RuntimeOrigin::signed(account(1)),
+			board,
+			thread,
+			H256::repeat_byte(9),
+		));
+		let buffer_index = BoardState::buffer_head(board) - 1;
+		// Deliberately leave the assigned shard without an elected attester set, so no commit
+		// can ever be submitted for this post.
+		let author_balance_before = Balances::free_balance(account(1));
+
+		run_to_block(System::block_number() + COMMIT_WINDOW + REVEAL_WINDOW + 2);
+
+		// The on_idle sweep must not reject/slash the post: it should still be in the buffer.
+		assert!(BufferedPosts::<Test>::get(board, buffer_index).is_some());
+		assert_eq!(Balances::free_balance(account(1)), author_balance_before);
+
+		assert_ok!(BoardState::reclaim_buffered_post(
+			RuntimeOrigin::signed(account(1)),
+			board,
+			buffer_index,
+		));
+
+		assert!(BufferedPosts::<Test>::get(board, buffer_index).is_none());
+		assert_eq!(Balances::free_balance(account(1)), author_balance_before + 10);
+	});
+}
+
+#[test]
+fn reclaim_fails_for_non_author_and_before_reveal_window_elapses() {
+	new_test_ext().execute_with(|| {
+		let board = create_board(4);
+		let thread = create_thread(board, H256::repeat_byte(1));
+
+		assert_ok!(BoardState::submit_post(
+			RuntimeOrigin::signed(account(1)),
+			board,
+			thread,
+			H256::repeat_byte(9),
+		));
+		let buffer_index = BoardState::buffer_head(board) - 1;
+
+		assert_noop!(
+			BoardState::reclaim_buffered_post(RuntimeOrigin::signed(account(2)), board, buffer_index),
+			Error::<Test>::NotAuthor,
+		);
+		assert_noop!(
+			BoardState::reclaim_buffered_post(RuntimeOrigin::signed(account(1)), board, buffer_index),
+			Error::<Test>::StillPending,
+		);
+	});
+}
+
+#[test]
+fn faulty_attester_is_slashed_and_honest_attesters_are_rewarded_from_the_slash() {
+	new_test_ext().execute_with(|| {
+		let board = create_board(4);
+		let thread = create_thread(board, H256::repeat_byte(1));
+
+		assert_ok!(BoardState::register_attester(RuntimeOrigin::signed(account(2))));
+		assert_ok!(BoardState::register_attester(RuntimeOrigin::signed(account(3))));
+		let honest_balance_before = Balances::free_balance(account(2));
+		let faulty_bonded_balance_before = Balances::reserved_balance(account(3));
+
+		assert_ok!(BoardState::submit_post(
+			RuntimeOrigin::signed(account(1)),
+			board,
+			thread,
+			H256::repeat_byte(9),
+		));
+		let buffer_index = BoardState::buffer_head(board) - 1;
+		let shard = BufferedPosts::<Test>::get(board, buffer_index).unwrap().shard;
+		set_shard_attesters(board, shard, vec![2, 3]);
+
+		// Attester 2 commits and reveals honestly; attester 3 never reveals.
+		let salt = [5u8; 32];
+		let commitment = commitment_for(Vote::True, salt, account(2), buffer_index);
+		assert_ok!(BoardState::commit_attestation(
+			RuntimeOrigin::signed(account(2)),
+			board,
+			buffer_index,
+			shard,
+			commitment,
+		));
+		run_to_block(System::block_number() + COMMIT_WINDOW + 1);
+		assert_ok!(BoardState::reveal_attestation(
+			RuntimeOrigin::signed(account(2)),
+			board,
+			buffer_index,
+			shard,
+			Vote::True,
+			salt,
+		));
+		run_to_block(System::block_number() + REVEAL_WINDOW + 1);
+
+		assert!(Balances::reserved_balance(account(3)) < faulty_bonded_balance_before, "faulty attester's bond must be slashed");
+		assert!(Balances::free_balance(account(2)) > honest_balance_before, "honest attester must be rewarded from the slash");
+	});
+}
+
+#[test]
+fn committee_stays_pinned_across_a_mid_attestation_reelection() {
+	new_test_ext().execute_with(|| {
+		let board = create_board(4);
+		let thread = create_thread(board, H256::repeat_byte(1));
+
+		assert_ok!(BoardState::submit_post(
+			RuntimeOrigin::signed(account(1)),
+			board,
+			thread,
+			H256::repeat_byte(9),
+		));
+		let buffer_index = BoardState::buffer_head(board) - 1;
+		let shard = BufferedPosts::<Test>::get(board, buffer_index).unwrap().shard;
+		set_shard_attesters(board, shard, vec![2, 3]);
+
+		let salt = [4u8; 32];
+		let commitment = commitment_for(Vote::True, salt, account(2), buffer_index);
+		assert_ok!(BoardState::commit_attestation(
+			RuntimeOrigin::signed(account(2)),
+			board,
+			buffer_index,
+			shard,
+			commitment,
+		));
+
+		// Force a shard re-election mid-attestation, straight from an attester pool that never
+		// contains account 2, so the live `ShardAttesters` no longer overlaps the committee
+		// this attestation was created against.
+		assert_ok!(BoardState::register_attester(RuntimeOrigin::signed(account(5))));
+		assert_ok!(BoardState::register_attester(RuntimeOrigin::signed(account(6))));
+		BoardState::on_initialize(1_000);
+		assert!(!ShardAttesters::<Test>::get(board, shard).unwrap().contains(&account(2)));
+
+		// account(2)'s reveal must still resolve against the committee pinned when the
+		// attestation was created, not the now-reelected live `ShardAttesters`.
+		run_to_block(System::block_number() + COMMIT_WINDOW + 1);
+		assert_ok!(BoardState::reveal_attestation(
+			RuntimeOrigin::signed(account(2)),
+			board,
+			buffer_index,
+			shard,
+			Vote::True,
+			salt,
+		));
+
+		let attestation = crate::Attestations::<Test>::get((board, buffer_index, shard)).unwrap();
+		assert_eq!(attestation.committee.len(), 2, "committee must stay pinned to its original size");
+	});
+}
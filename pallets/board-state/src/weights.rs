@@ -0,0 +1,83 @@
+// This file is part of the board-state pallet.
+
+//! Hand-written weights for `pallet_board_state`.
+//!
+//! Every dispatchable is given the same flat weight of `10_000_000` ref-time; these values do not
+//! come from a benchmarking run.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+use frame_support::weights::Weight;
+
+/// Weight functions needed for `pallet_board_state`.
+pub trait WeightInfo {
+	fn create_board() -> Weight;
+	fn create_thread() -> Weight;
+	fn submit_post() -> Weight;
+	fn commit_attestation() -> Weight;
+	fn reveal_attestation() -> Weight;
+	fn register_attester() -> Weight;
+	fn unregister_attester() -> Weight;
+	fn reclaim_buffered_post() -> Weight;
+}
+
+/// Flat placeholder weights for `pallet_board_state`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn create_board() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+	fn create_thread() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+	fn submit_post() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+	fn commit_attestation() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+	fn reveal_attestation() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+	fn register_attester() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+	fn unregister_attester() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+	fn reclaim_buffered_post() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn create_board() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+	fn create_thread() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+	fn submit_post() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+	fn commit_attestation() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+	fn reveal_attestation() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+	fn register_attester() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+	fn unregister_attester() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+	fn reclaim_buffered_post() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+	}
+}